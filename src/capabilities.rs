@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::encoding::OffsetEncoding;
+use crate::jsonrpc::LspMethod;
+
+/// The capabilities a language server advertised in its `initialize`
+/// response. The client consults these before issuing a request, since some
+/// servers crash or return an error when sent a method they did not
+/// advertise support for.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#serverCapabilities)
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ServerCapabilities {
+    /// The position encoding the server chose from the client's advertised
+    /// `general.positionEncodings`. Absent when the server predates this
+    /// negotiation, in which case the spec mandates UTF-16.
+    #[serde(rename = "positionEncoding", default)]
+    pub position_encoding: Option<OffsetEncoding>,
+    #[serde(rename = "hoverProvider", default)]
+    pub hover_provider: Option<Value>,
+    #[serde(rename = "completionProvider", default)]
+    pub completion_provider: Option<Value>,
+    #[serde(rename = "signatureHelpProvider", default)]
+    pub signature_help_provider: Option<Value>,
+    #[serde(rename = "definitionProvider", default)]
+    pub definition_provider: Option<Value>,
+    #[serde(rename = "referencesProvider", default)]
+    pub references_provider: Option<Value>,
+    #[serde(rename = "documentSymbolProvider", default)]
+    pub document_symbol_provider: Option<Value>,
+    #[serde(rename = "documentHighlightProvider", default)]
+    pub document_highlight_provider: Option<Value>,
+    #[serde(rename = "documentFormattingProvider", default)]
+    pub document_formatting_provider: Option<Value>,
+    #[serde(rename = "documentRangeFormattingProvider", default)]
+    pub document_range_formatting_provider: Option<Value>,
+    #[serde(rename = "documentOnTypeFormattingProvider", default)]
+    pub document_on_type_formatting_provider: Option<Value>,
+    #[serde(rename = "codeActionProvider", default)]
+    pub code_action_provider: Option<Value>,
+    #[serde(rename = "workspaceSymbolProvider", default)]
+    pub workspace_symbol_provider: Option<Value>,
+    #[serde(rename = "renameProvider", default)]
+    pub rename_provider: Option<Value>,
+    #[serde(rename = "executeCommandProvider", default)]
+    pub execute_command_provider: Option<Value>,
+}
+
+impl ServerCapabilities {
+    /// Returns whether the server advertises support for `method`. Methods
+    /// with no corresponding capability field (e.g. `initialize`, `shutdown`,
+    /// and the `textDocument/did*` notifications) are always considered
+    /// supported.
+    pub fn supports(&self, method: &LspMethod) -> bool {
+        match method {
+            LspMethod::Hover => Self::is_enabled(&self.hover_provider),
+            LspMethod::Completion => Self::is_enabled(&self.completion_provider),
+            LspMethod::CompletionItemResolve => {
+                Self::completion_resolve_enabled(&self.completion_provider)
+            }
+            LspMethod::SignatureHelp => Self::is_enabled(&self.signature_help_provider),
+            LspMethod::Definition => Self::is_enabled(&self.definition_provider),
+            LspMethod::References => Self::is_enabled(&self.references_provider),
+            LspMethod::DocumentSymbol => Self::is_enabled(&self.document_symbol_provider),
+            LspMethod::DocumentHighlights => Self::is_enabled(&self.document_highlight_provider),
+            LspMethod::DocumentFormatting => Self::is_enabled(&self.document_formatting_provider),
+            LspMethod::RangeFormatting => Self::is_enabled(&self.document_range_formatting_provider),
+            LspMethod::OnTypeFormatting => {
+                Self::is_enabled(&self.document_on_type_formatting_provider)
+            }
+            LspMethod::CodeAction => Self::is_enabled(&self.code_action_provider),
+            LspMethod::WorkspaceSymbol => Self::is_enabled(&self.workspace_symbol_provider),
+            LspMethod::Rename | LspMethod::PrepareRename => Self::is_enabled(&self.rename_provider),
+            LspMethod::ExecuteCommand => Self::is_enabled(&self.execute_command_provider),
+            LspMethod::Initialize
+            | LspMethod::Shutdown
+            | LspMethod::DidChange
+            | LspMethod::DidOpen
+            | LspMethod::DidClose
+            | LspMethod::WorkspaceReferences => true,
+        }
+    }
+
+    /// A provider field is considered enabled when present and not
+    /// explicitly `false`; servers may advertise either a boolean or an
+    /// options object for the same field.
+    fn is_enabled(provider: &Option<Value>) -> bool {
+        match provider {
+            None => false,
+            Some(Value::Bool(enabled)) => *enabled,
+            Some(_) => true,
+        }
+    }
+
+    /// Unlike the other capability flags, `completionProvider.resolveProvider`
+    /// is nested one level deeper: a server can support completion without
+    /// supporting the `completionItem/resolve` follow-up, so the mere
+    /// presence of `completionProvider` does not imply resolve is safe to
+    /// send.
+    fn completion_resolve_enabled(completion_provider: &Option<Value>) -> bool {
+        completion_provider
+            .as_ref()
+            .and_then(|value| value.get("resolveProvider"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+}
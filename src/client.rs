@@ -0,0 +1,369 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use tokio::sync::{oneshot, Mutex};
+
+use crate::capabilities::ServerCapabilities;
+use crate::encoding::OffsetEncoding;
+use crate::jsonrpc::{JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use crate::request::{
+    CancelNotification, CompletionItemResolveRequest, CompletionRequest, DidChangeNotification,
+    DidCloseNotification, DidOpenNotification, DocumentFormattingRequest, HoverRequest,
+    Notification, RenameRequest, Request,
+};
+use crate::transport::{Transport, TransportSender};
+use crate::types::{
+    CancelParams, CompletionItem, CompletionList, CompletionParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentFormattingParams, Hover,
+    HoverParams, Position, RenameParams, TextEdit, WorkspaceEdit,
+};
+
+/// Responses awaiting their caller, keyed by request id. `None` once the
+/// transport has disconnected, so requests made afterwards fail immediately
+/// instead of waiting on a reply that will never arrive.
+type PendingResponses = Arc<Mutex<Option<HashMap<u64, oneshot::Sender<Result<JsonRpcResponse<Value>>>>>>>;
+
+/// A connected language server: owns the means to write to its `Transport`
+/// and the `ServerCapabilities` negotiated during `initialize`, gates
+/// outgoing requests on those capabilities, and routes each incoming
+/// response to the request that is awaiting it.
+pub struct Client {
+    sender: TransportSender,
+    capabilities: ServerCapabilities,
+    /// The position encoding negotiated with the server during `initialize`,
+    /// i.e. what `Position::character` is measured in for every request
+    /// this client sends.
+    offset_encoding: OffsetEncoding,
+    next_id: AtomicU64,
+    pending: PendingResponses,
+    /// Completion items a `completionItem/resolve` has already been sent
+    /// for, so a tight render loop does not issue a second one.
+    resolved_items: Arc<Mutex<HashSet<u64>>>,
+}
+
+impl Client {
+    /// Wraps `transport` together with the `ServerCapabilities` returned from
+    /// the `initialize` handshake, and spawns a background task that reads
+    /// `transport` and routes each response to the pending request that is
+    /// awaiting it.
+    ///
+    /// The offset encoding used for all `Position` conversions is taken from
+    /// `capabilities.position_encoding`, falling back to the spec-mandated
+    /// UTF-16 default if the server did not choose one.
+    pub fn new(mut transport: Transport, capabilities: ServerCapabilities) -> Self {
+        let sender = transport.sender();
+        let pending: PendingResponses = Arc::new(Mutex::new(Some(HashMap::new())));
+        let routing_pending = Arc::clone(&pending);
+        let offset_encoding = capabilities.position_encoding.unwrap_or_default();
+
+        tokio::spawn(async move {
+            while let Some(body) = transport.recv_raw().await {
+                match serde_json::from_str::<JsonRpcResponse<Value>>(&body) {
+                    Ok(response) => {
+                        let mut guard = routing_pending.lock().await;
+                        if let Some(tx) = guard.as_mut().and_then(|map| map.remove(&response.id)) {
+                            let _ = tx.send(Ok(response));
+                        }
+                    }
+                    Err(_) => {
+                        // Not a response to one of our requests — most
+                        // likely a server-initiated notification
+                        // (`window/logMessage`, `$/progress`,
+                        // `textDocument/publishDiagnostics`, ...), which
+                        // this client does not yet handle. Ignore it rather
+                        // than treating a message shape we don't understand
+                        // as a transport disconnect.
+                        eprintln!("lsp-study: ignoring unparseable message from language server: {body}");
+                    }
+                }
+            }
+
+            // The transport itself is gone (the reader task exited because
+            // the child's stdout closed): nothing will ever satisfy the
+            // waiters still in `pending`, so fail them explicitly instead of
+            // letting their oneshot receivers hang forever. Setting
+            // `pending` to `None` also makes every later `request()`/
+            // `cancel()` call fail fast rather than queue up behind a dead
+            // connection.
+            if let Some(map) = routing_pending.lock().await.take() {
+                for (_, tx) in map {
+                    let _ = tx.send(Err(anyhow!("language server disconnected")));
+                }
+            }
+        });
+
+        Self {
+            sender,
+            capabilities,
+            offset_encoding,
+            next_id: AtomicU64::new(1),
+            pending,
+            resolved_items: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Converts a byte offset into `text` to a `Position` under the
+    /// negotiated offset encoding, so positions for non-ASCII documents are
+    /// computed the way the server expects rather than assuming byte
+    /// offsets are code units.
+    pub fn position_at(&self, text: &str, byte_offset: usize) -> Position {
+        self.offset_encoding.position_at(text, byte_offset)
+    }
+
+    /// Converts a `Position` received from or sent to the server back to a
+    /// byte offset into `text`, under the negotiated offset encoding.
+    pub fn offset_at(&self, text: &str, position: Position) -> usize {
+        self.offset_encoding.offset_at(text, position)
+    }
+
+    /// Allocates the next request id.
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Sends a typed request for `M` and waits for its response, or returns
+    /// `None` without contacting the server if the server's capabilities do
+    /// not advertise support for `M::LSP_METHOD`.
+    pub async fn request<M: Request>(&self, params: M::Params) -> Result<Option<M::Result>> {
+        if !self.capabilities.supports(&M::LSP_METHOD) {
+            return Ok(None);
+        }
+
+        let id = self.next_id();
+        let (tx, rx) = oneshot::channel();
+        match self.pending.lock().await.as_mut() {
+            Some(map) => {
+                map.insert(id, tx);
+            }
+            None => return Err(anyhow!("language server disconnected")),
+        }
+
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            method: M::METHOD,
+            params,
+            id,
+        };
+        if let Err(err) = self.sender.send(&JsonRpcMessage::Request(request)) {
+            if let Some(map) = self.pending.lock().await.as_mut() {
+                map.remove(&id);
+            }
+            return Err(err);
+        }
+
+        let response = rx
+            .await
+            .map_err(|_| anyhow!("response channel closed before a reply arrived"))??;
+        if let Some(error) = response.error {
+            return Err(anyhow!("{} (code {})", error.message, error.code));
+        }
+        let result = response.result.map(serde_json::from_value).transpose()?;
+        Ok(result)
+    }
+
+    /// Sends a typed notification for `N`. Notifications have no response.
+    pub fn notify<N: Notification>(&self, params: N::Params) -> Result<()> {
+        let notification = JsonRpcNotification {
+            jsonrpc: "2.0",
+            method: N::METHOD,
+            params,
+        };
+        self.sender.send(&JsonRpcMessage::Notification(notification))
+    }
+
+    /// Requests hover information, or `None` if the server does not
+    /// advertise `hoverProvider`.
+    pub async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        Ok(self.request::<HoverRequest>(params).await?.flatten())
+    }
+
+    /// Requests completion suggestions, or `None` if the server does not
+    /// advertise `completionProvider`.
+    pub async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionList>> {
+        Ok(self.request::<CompletionRequest>(params).await?.flatten())
+    }
+
+    /// Requests a symbol rename, or `None` if the server does not advertise
+    /// `renameProvider`.
+    pub async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        Ok(self.request::<RenameRequest>(params).await?.flatten())
+    }
+
+    /// Requests document formatting, or `None` if the server does not
+    /// advertise `documentFormattingProvider`.
+    pub async fn formatting(
+        &self,
+        params: DocumentFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        Ok(self.request::<DocumentFormattingRequest>(params).await?.flatten())
+    }
+
+    /// Notifies the server that a document was opened.
+    pub fn did_open(&self, params: DidOpenTextDocumentParams) -> Result<()> {
+        self.notify::<DidOpenNotification>(params)
+    }
+
+    /// Notifies the server that a document changed.
+    pub fn did_change(&self, params: DidChangeTextDocumentParams) -> Result<()> {
+        self.notify::<DidChangeNotification>(params)
+    }
+
+    /// Notifies the server that a document was closed.
+    pub fn did_close(&self, params: DidCloseTextDocumentParams) -> Result<()> {
+        self.notify::<DidCloseNotification>(params)
+    }
+
+    /// Requests `completionItem/resolve` for the item identified by
+    /// `item_id`, unless a resolve for it has already been sent. Marking the
+    /// item resolved before the request completes means a resolve that
+    /// errors is not retried either, which would otherwise flood the server
+    /// from a tight render loop.
+    pub async fn resolve_completion_item(
+        &self,
+        item_id: u64,
+        params: CompletionItem,
+    ) -> Result<Option<CompletionItem>> {
+        {
+            let mut resolved = self.resolved_items.lock().await;
+            if !resolved.insert(item_id) {
+                return Ok(None);
+            }
+        }
+
+        self.request::<CompletionItemResolveRequest>(params).await
+    }
+
+    /// Cancels the in-flight request `id`: resolves its waiter locally with
+    /// a cancellation error and sends a `$/cancelRequest` notification so the
+    /// server knows too. LSP cannot guarantee a server will actually stop
+    /// work on a cancelled request (or reply to it at all), so the waiter is
+    /// resolved here rather than left in `pending` for a response that may
+    /// never come.
+    pub async fn cancel(&self, id: u64) -> Result<()> {
+        if let Some(map) = self.pending.lock().await.as_mut() {
+            if let Some(tx) = map.remove(&id) {
+                let _ = tx.send(Err(anyhow!("request {id} was cancelled")));
+            }
+        }
+        self.notify::<CancelNotification>(CancelParams { id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::request::Initialize;
+    use crate::types::{InitializeParams, TextDocumentItem};
+
+    #[tokio::test]
+    async fn disconnect_fails_in_flight_request_instead_of_hanging() {
+        // `true` exits immediately, so the transport disconnects right after
+        // the client is constructed.
+        let transport = Transport::spawn("true", &[]).await.unwrap();
+        let client = Client::new(transport, ServerCapabilities::default());
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.request::<Initialize>(InitializeParams::default()),
+        )
+        .await
+        .expect("request hung instead of failing on disconnect");
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn unparseable_messages_do_not_disconnect_the_client() {
+        let transport = Transport::spawn("cat", &[]).await.unwrap();
+        let client = Client::new(transport, ServerCapabilities::default());
+
+        // Simulate an outstanding request so we can tell whether the
+        // routing task tears `pending` down.
+        let (tx, _rx) = oneshot::channel();
+        client.pending.lock().await.as_mut().unwrap().insert(7, tx);
+
+        // `cat` echoes this verbatim. It has no `id` field, just like a
+        // server-initiated notification (`window/logMessage`, `$/progress`,
+        // `textDocument/publishDiagnostics`, ...) would — it should be
+        // ignored, not mistaken for a transport disconnect.
+        client
+            .notify::<DidOpenNotification>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///test".to_string(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: String::new(),
+                },
+            })
+            .unwrap();
+
+        // Give the routing task a chance to process the echoed message.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert!(client
+            .pending
+            .lock()
+            .await
+            .as_ref()
+            .expect("pending was torn down by an unrelated parse failure")
+            .contains_key(&7));
+    }
+
+    #[tokio::test]
+    async fn cancel_resolves_the_waiter_locally() {
+        let transport = Transport::spawn("cat", &[]).await.unwrap();
+        let client = Client::new(transport, ServerCapabilities::default());
+
+        let (tx, rx) = oneshot::channel();
+        client.pending.lock().await.as_mut().unwrap().insert(1, tx);
+
+        client.cancel(1).await.unwrap();
+
+        assert!(rx.await.unwrap().is_err());
+        assert!(!client
+            .pending
+            .lock()
+            .await
+            .as_ref()
+            .unwrap()
+            .contains_key(&1));
+    }
+
+    #[tokio::test]
+    async fn completion_item_resolve_is_sent_at_most_once() {
+        // `true` exits immediately, so the one resolve request that does go
+        // out fails on disconnect rather than hanging.
+        let transport = Transport::spawn("true", &[]).await.unwrap();
+        let capabilities = ServerCapabilities {
+            completion_provider: Some(json!({ "resolveProvider": true })),
+            ..Default::default()
+        };
+        let client = Client::new(transport, capabilities);
+        let item = CompletionItem {
+            label: "foo".to_string(),
+            detail: None,
+            documentation: None,
+            data: None,
+        };
+
+        let first = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.resolve_completion_item(42, item.clone()),
+        )
+        .await
+        .expect("resolve hung instead of failing on disconnect");
+        assert!(first.is_err());
+        assert!(client.resolved_items.lock().await.contains(&42));
+
+        // A second resolve for the same item short-circuits on
+        // `resolved_items` without attempting to contact the server again.
+        assert_eq!(client.resolve_completion_item(42, item).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::Position;
+
+/// The unit a server counts `Position::character` in. The LSP base
+/// specification defaults to UTF-16 code units for historical reasons
+/// (most existing language servers are written against UTF-16 string
+/// APIs), but also lets client and server negotiate UTF-8 or UTF-32
+/// during `initialize` via `general.positionEncodings` /
+/// `capabilities.positionEncoding`.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#positionEncodingKind)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OffsetEncoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "utf-16")]
+    Utf16,
+    #[serde(rename = "utf-32")]
+    Utf32,
+}
+
+impl Default for OffsetEncoding {
+    /// The spec-mandated default when a server does not advertise
+    /// `positionEncoding` in its `initialize` result.
+    fn default() -> Self {
+        OffsetEncoding::Utf16
+    }
+}
+
+impl OffsetEncoding {
+    /// Converts a byte offset into `text` to an LSP `Position` under this
+    /// encoding.
+    ///
+    /// `text` is assumed to use `\n` line endings. Panics if `byte_offset`
+    /// does not fall on a UTF-8 character boundary.
+    pub fn position_at(&self, text: &str, byte_offset: usize) -> Position {
+        let byte_offset = byte_offset.min(text.len());
+        let preceding = &text[..byte_offset];
+        let line = preceding.matches('\n').count() as u32;
+        let line_start = preceding.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        let line_text = &text[line_start..byte_offset];
+        let character = match self {
+            OffsetEncoding::Utf8 => line_text.len() as u32,
+            OffsetEncoding::Utf16 => line_text.encode_utf16().count() as u32,
+            OffsetEncoding::Utf32 => line_text.chars().count() as u32,
+        };
+
+        Position { line, character }
+    }
+
+    /// Converts an LSP `Position` back to a byte offset into `text` under
+    /// this encoding. Clamps to the end of the line (or the end of `text`)
+    /// if `position` points past it, which is more forgiving than panicking
+    /// on a slightly-stale position.
+    pub fn offset_at(&self, text: &str, position: Position) -> usize {
+        let line_start = if position.line == 0 {
+            0
+        } else {
+            text.match_indices('\n')
+                .nth(position.line as usize - 1)
+                .map(|(i, _)| i + 1)
+                .unwrap_or(text.len())
+        };
+        let line_end = text[line_start..]
+            .find('\n')
+            .map(|i| line_start + i)
+            .unwrap_or(text.len());
+        let line_text = &text[line_start..line_end];
+
+        let byte_in_line = match self {
+            OffsetEncoding::Utf8 => (position.character as usize).min(line_text.len()),
+            OffsetEncoding::Utf16 => {
+                let mut units = 0u32;
+                let mut bytes = 0usize;
+                for ch in line_text.chars() {
+                    if units >= position.character {
+                        break;
+                    }
+                    units += ch.len_utf16() as u32;
+                    bytes += ch.len_utf8();
+                }
+                bytes
+            }
+            OffsetEncoding::Utf32 => line_text
+                .char_indices()
+                .nth(position.character as usize)
+                .map(|(i, _)| i)
+                .unwrap_or(line_text.len()),
+        };
+
+        line_start + byte_in_line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_position_and_offset_round_trip() {
+        let text = "hello\nworld";
+        // Byte offset 8 is the 'r' in "world" (line 1, column 2).
+        for encoding in [
+            OffsetEncoding::Utf8,
+            OffsetEncoding::Utf16,
+            OffsetEncoding::Utf32,
+        ] {
+            let position = encoding.position_at(text, 8);
+            assert_eq!(position.line, 1);
+            assert_eq!(position.character, 2);
+            assert_eq!(encoding.offset_at(text, position), 8);
+        }
+    }
+
+    #[test]
+    fn multibyte_line_counts_units_per_encoding() {
+        // "🦀" is 4 UTF-8 bytes, a UTF-16 surrogate pair (2 code units), and
+        // a single UTF-32 code point.
+        let text = "🦀b\nx";
+        let byte_offset = "🦀b".len();
+
+        let utf8 = OffsetEncoding::Utf8.position_at(text, byte_offset);
+        assert_eq!((utf8.line, utf8.character), (0, 5));
+        assert_eq!(OffsetEncoding::Utf8.offset_at(text, utf8), byte_offset);
+
+        let utf16 = OffsetEncoding::Utf16.position_at(text, byte_offset);
+        assert_eq!((utf16.line, utf16.character), (0, 3));
+        assert_eq!(OffsetEncoding::Utf16.offset_at(text, utf16), byte_offset);
+
+        let utf32 = OffsetEncoding::Utf32.position_at(text, byte_offset);
+        assert_eq!((utf32.line, utf32.character), (0, 2));
+        assert_eq!(OffsetEncoding::Utf32.offset_at(text, utf32), byte_offset);
+    }
+
+    #[test]
+    fn offset_at_clamps_position_past_end_of_line() {
+        let text = "ab\ncd";
+        let past_eol = Position {
+            line: 0,
+            character: 100,
+        };
+        assert_eq!(OffsetEncoding::Utf8.offset_at(text, past_eol), 2);
+        assert_eq!(OffsetEncoding::Utf16.offset_at(text, past_eol), 2);
+        assert_eq!(OffsetEncoding::Utf32.offset_at(text, past_eol), 2);
+    }
+
+    #[test]
+    fn position_at_clamps_byte_offset_past_end_of_text() {
+        let text = "ab";
+        let position = OffsetEncoding::Utf8.position_at(text, 100);
+        assert_eq!((position.line, position.character), (0, 2));
+    }
+
+    #[test]
+    fn offset_at_rounds_mid_surrogate_pair_position_up_to_the_next_character() {
+        // Under UTF-16, character 1 falls between "🦀"'s two surrogate
+        // halves. There is no valid byte offset there, so this should land
+        // on the next character boundary (the start of "b") rather than
+        // inside the emoji's UTF-8 encoding.
+        let text = "🦀b";
+        let position = Position {
+            line: 0,
+            character: 1,
+        };
+        assert_eq!(OffsetEncoding::Utf16.offset_at(text, position), "🦀".len());
+    }
+}
@@ -2,7 +2,6 @@ use serde::{Serialize, Deserialize};
 
 /// Represents a Language Server Protocol method.
 /// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/)
-#[derive(Serialize)]
 pub enum LspMethod {
     /// Initialize the language server.
     /// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#initialize)
@@ -25,6 +24,9 @@ pub enum LspMethod {
     /// Request the language server to provide completion suggestions for a given position in a document.
     /// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_completion)
     Completion,
+    /// Request the language server to resolve additional information for a completion item.
+    /// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#completionItem_resolve)
+    CompletionItemResolve,
     /// Request the language server to provide signature help for a given position in a document.
     /// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_signatureHelp)
     SignatureHelp,
@@ -69,81 +71,55 @@ pub enum LspMethod {
     ExecuteCommand,
 }
 
-impl LspMethod {
-    /// Converts the `LspMethod` variant to a string.
-    pub fn to_string(&self) -> &'static str {
-        match self {
-            LspMethod::Initialize => "initialize",
-            LspMethod::Shutdown => "shutdown",
-            LspMethod::DidChange => "textDocument/didChange",
-            LspMethod::DidOpen => "textDocument/didOpen",
-            LspMethod::DidClose => "textDocument/didClose",
-            LspMethod::Hover => "textDocument/hover",
-            LspMethod::Completion => "textDocument/completion",
-            LspMethod::SignatureHelp => "textDocument/signatureHelp",
-            LspMethod::Definition => "textDocument/definition",
-            LspMethod::References => "textDocument/references",
-            LspMethod::DocumentSymbol => "textDocument/documentSymbol",
-            LspMethod::DocumentHighlights => "textDocument/documentHighlights",
-            LspMethod::DocumentFormatting => "textDocument/formatting",
-            LspMethod::RangeFormatting => "textDocument/rangeFormatting",
-            LspMethod::OnTypeFormatting => "textDocument/onTypeFormatting",
-            LspMethod::CodeAction => "textDocument/codeAction",
-            LspMethod::Rename => "textDocument/rename",
-            LspMethod::PrepareRename => "textDocument/prepareRename",
-            LspMethod::WorkspaceSymbol => "workspace/symbol",
-            LspMethod::WorkspaceReferences => "workspace/references",
-            LspMethod::ExecuteCommand => "workspace/executeCommand",
-        }
-    }
-
-    /// Converts the string representation of an LSP method to the corresponding `LspMethod` variant.
-    pub fn from_str(s: &str) -> Option<LspMethod> {
-        match s {
-            "initialize" => Some(LspMethod::Initialize),
-            "shutdown" => Some(LspMethod::Shutdown),
-            "textDocument/didChange" => Some(LspMethod::DidChange),
-            "textDocument/didOpen" => Some(LspMethod::DidOpen),
-            "textDocument/didClose" => Some(LspMethod::DidClose),
-            "textDocument/hover" => Some(LspMethod::Hover),
-            "textDocument/completion" => Some(LspMethod::Completion),
-            "textDocument/signatureHelp" => Some(LspMethod::SignatureHelp),
-            "textDocument/definition" => Some(LspMethod::Definition),
-            "textDocument/references" => Some(LspMethod::References),
-            "textDocument/documentSymbol" => Some(LspMethod::DocumentSymbol),
-            "textDocument/documentHighlights" => Some(LspMethod::DocumentHighlights),
-            "textDocument/formatting" => Some(LspMethod::DocumentFormatting),
-            "textDocument/rangeFormatting" => Some(LspMethod::RangeFormatting),
-            "textDocument/onTypeFormatting" => Some(LspMethod::OnTypeFormatting),
-            "textDocument/codeAction" => Some(LspMethod::CodeAction),
-            "workspace/symbol" => Some(LspMethod::WorkspaceSymbol),
-            "workspace/references" => Some(LspMethod::WorkspaceReferences),
-            "textDocument/rename" => Some(LspMethod::Rename),
-            "textDocument/prepareRename" => Some(LspMethod::PrepareRename),
-            "workspace/executeCommand" => Some(LspMethod::ExecuteCommand),
-            _ => None,
-        }
-    }
-}
-
 /// Represents a JSON-RPC (Remote Procedure Call) request.
+///
+/// `params` is the single structured object LSP methods expect, not a JSON
+/// array: the base JSON-RPC spec allows either shape, but LSP always uses
+/// an object (e.g. `InitializeParams`, `HoverParams`).
 #[derive(Serialize)]
 pub struct JsonRpcRequest<'a, T: Serialize> {
     /// Specifies the version of the JSON-RPC protocol. It must always be set to `"2.0"`.
     pub jsonrpc: &'a str,
     /// Specifies the name of the method to be invoked on the remote server.
     pub method: &'a str,
-    /// Contains the parameters to be passed to the method specified in the `method` field.
-    pub params: Vec<T>,
+    /// The parameters to pass to the method specified in the `method` field.
+    pub params: T,
     /// An identifier for the request. This is used to match responses to their corresponding requests.
     pub id: u64,
 }
 
+/// Represents a JSON-RPC notification, i.e. a request with no `id` that the
+/// server is not expected to reply to.
+#[derive(Serialize)]
+pub struct JsonRpcNotification<'a, T: Serialize> {
+    /// Specifies the version of the JSON-RPC protocol. It must always be set to `"2.0"`.
+    pub jsonrpc: &'a str,
+    /// Specifies the name of the method to be invoked on the remote server.
+    pub method: &'a str,
+    /// The parameters to pass to the method specified in the `method` field.
+    pub params: T,
+}
+
+/// Wraps either a `JsonRpcRequest` or a `JsonRpcNotification` so the
+/// transport can serialize whichever shape the `LspMethod` being sent
+/// actually requires.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum JsonRpcMessage<'a, T: Serialize> {
+    Request(JsonRpcRequest<'a, T>),
+    Notification(JsonRpcNotification<'a, T>),
+}
+
 /// Represents a JSON-RPC response.
-#[derive(Deserialize)]
-pub struct JsonRpcResponse<'a, T: Serialize> {
+///
+/// Unlike `JsonRpcRequest`, this owns its `jsonrpc` string rather than
+/// borrowing it, since responses are routed to their awaiter through a
+/// channel and must not be tied to the lifetime of the buffer they were
+/// decoded from.
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcResponse<T> {
     /// Specifies the version of the JSON-RPC protocol. It should always be "2.0".
-    pub jsonrpc: &'a str,
+    pub jsonrpc: String,
     /// Contains the result of a successful JSON-RPC request, if applicable.
     pub result: Option<T>,
     /// Contains the error details in case the JSON-RPC request encounters an error.
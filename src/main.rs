@@ -1,11 +1,20 @@
 use anyhow::Result;
+mod capabilities;
+mod client;
+mod encoding;
 mod jsonrpc;
+mod request;
+mod transport;
+mod types;
+
+use request::{Initialize, Request};
+use types::InitializeParams;
 
 fn main() -> Result<()> {
-    let json_rpc_request: jsonrpc::JsonRpcRequest<'static, &str> = jsonrpc::JsonRpcRequest {
+    let json_rpc_request = jsonrpc::JsonRpcRequest {
         jsonrpc: "2.0",
-        method: jsonrpc::LspMethod::Initialize.to_string(),
-        params: vec![],
+        method: Initialize::METHOD,
+        params: InitializeParams::default(),
         id: 1,
     };
 
@@ -0,0 +1,111 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::jsonrpc::LspMethod;
+use crate::types::{
+    CancelParams, CompletionItem, CompletionList, CompletionParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentFormattingParams, Hover,
+    HoverParams, InitializeParams, InitializeResult, RenameParams, TextEdit, WorkspaceEdit,
+};
+
+/// Associates an LSP request method with its concrete parameter and result
+/// types, so a caller can go through `Client::request::<M>` instead of
+/// serializing an untyped JSON array by hand.
+pub trait Request {
+    /// The JSON-RPC method name, e.g. `"textDocument/hover"`.
+    const METHOD: &'static str;
+    /// The `LspMethod` this request corresponds to, used to gate it on
+    /// `ServerCapabilities`.
+    const LSP_METHOD: LspMethod;
+    type Params: Serialize;
+    type Result: DeserializeOwned;
+}
+
+/// Associates an LSP notification method with its concrete parameter type.
+pub trait Notification {
+    /// The JSON-RPC method name, e.g. `"textDocument/didOpen"`.
+    const METHOD: &'static str;
+    type Params: Serialize;
+}
+
+/// The `initialize` request.
+pub struct Initialize;
+impl Request for Initialize {
+    const METHOD: &'static str = "initialize";
+    const LSP_METHOD: LspMethod = LspMethod::Initialize;
+    type Params = InitializeParams;
+    type Result = InitializeResult;
+}
+
+/// The `textDocument/hover` request.
+pub struct HoverRequest;
+impl Request for HoverRequest {
+    const METHOD: &'static str = "textDocument/hover";
+    const LSP_METHOD: LspMethod = LspMethod::Hover;
+    type Params = HoverParams;
+    type Result = Option<Hover>;
+}
+
+/// The `textDocument/completion` request.
+pub struct CompletionRequest;
+impl Request for CompletionRequest {
+    const METHOD: &'static str = "textDocument/completion";
+    const LSP_METHOD: LspMethod = LspMethod::Completion;
+    type Params = CompletionParams;
+    type Result = Option<CompletionList>;
+}
+
+/// The `completionItem/resolve` request.
+pub struct CompletionItemResolveRequest;
+impl Request for CompletionItemResolveRequest {
+    const METHOD: &'static str = "completionItem/resolve";
+    const LSP_METHOD: LspMethod = LspMethod::CompletionItemResolve;
+    type Params = CompletionItem;
+    type Result = CompletionItem;
+}
+
+/// The `textDocument/rename` request.
+pub struct RenameRequest;
+impl Request for RenameRequest {
+    const METHOD: &'static str = "textDocument/rename";
+    const LSP_METHOD: LspMethod = LspMethod::Rename;
+    type Params = RenameParams;
+    type Result = Option<WorkspaceEdit>;
+}
+
+/// The `textDocument/formatting` request.
+pub struct DocumentFormattingRequest;
+impl Request for DocumentFormattingRequest {
+    const METHOD: &'static str = "textDocument/formatting";
+    const LSP_METHOD: LspMethod = LspMethod::DocumentFormatting;
+    type Params = DocumentFormattingParams;
+    type Result = Option<Vec<TextEdit>>;
+}
+
+/// The `textDocument/didOpen` notification.
+pub struct DidOpenNotification;
+impl Notification for DidOpenNotification {
+    const METHOD: &'static str = "textDocument/didOpen";
+    type Params = DidOpenTextDocumentParams;
+}
+
+/// The `textDocument/didChange` notification.
+pub struct DidChangeNotification;
+impl Notification for DidChangeNotification {
+    const METHOD: &'static str = "textDocument/didChange";
+    type Params = DidChangeTextDocumentParams;
+}
+
+/// The `textDocument/didClose` notification.
+pub struct DidCloseNotification;
+impl Notification for DidCloseNotification {
+    const METHOD: &'static str = "textDocument/didClose";
+    type Params = DidCloseTextDocumentParams;
+}
+
+/// The `$/cancelRequest` notification.
+pub struct CancelNotification;
+impl Notification for CancelNotification {
+    const METHOD: &'static str = "$/cancelRequest";
+    type Params = CancelParams;
+}
@@ -0,0 +1,209 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::mpsc;
+
+use crate::jsonrpc::JsonRpcMessage;
+
+/// Spawns a language server as a child process and exchanges LSP base
+/// protocol framed JSON-RPC messages with it over its stdio.
+///
+/// Each JSON-RPC payload is framed with a header block terminated by
+/// `\r\n\r\n`, the mandatory header being `Content-Length: <N>\r\n` where `N`
+/// is the UTF-8 byte length of the JSON body.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#baseProtocol)
+pub struct Transport {
+    /// Kept alive for the lifetime of the transport so the language server
+    /// process is killed when the transport is dropped.
+    child: Child,
+    writer_tx: mpsc::UnboundedSender<String>,
+    reader_rx: mpsc::UnboundedReceiver<String>,
+}
+
+impl Transport {
+    /// Spawns `command` with `args` as a language server child process
+    /// (stdin/stdout/stderr piped, killed on drop) and starts the
+    /// reader/writer background tasks.
+    pub async fn spawn(command: &str, args: &[&str]) -> Result<Self> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to spawn language server `{command}`"))?;
+
+        let stdin = child.stdin.take().context("child stdin was not piped")?;
+        let stdout = child.stdout.take().context("child stdout was not piped")?;
+
+        let (writer_tx, writer_rx) = mpsc::unbounded_channel::<String>();
+        let (reader_tx, reader_rx) = mpsc::unbounded_channel::<String>();
+
+        tokio::spawn(writer_loop(stdin, writer_rx));
+        tokio::spawn(reader_loop(stdout, reader_tx));
+
+        Ok(Self {
+            child,
+            writer_tx,
+            reader_rx,
+        })
+    }
+
+    /// Returns a cloneable handle that can send framed messages independently
+    /// of this `Transport`, so a caller can keep writing after moving the
+    /// `Transport` itself into a background task that only reads.
+    pub fn sender(&self) -> TransportSender {
+        TransportSender(self.writer_tx.clone())
+    }
+
+    /// Waits for the next framed message from the language server, returning
+    /// its raw JSON body. Returns `None` once the transport has disconnected
+    /// (the reader task exited because the child's stdout closed or a frame
+    /// failed to parse at the base-protocol level).
+    ///
+    /// This deliberately stops at the raw body rather than deserializing
+    /// into a caller-chosen type: a body that fails to deserialize as, say,
+    /// a `JsonRpcResponse` is not a transport disconnect — it may just be a
+    /// server-initiated notification sharing the same stdout stream — and
+    /// conflating the two tears down the connection over a message shape
+    /// the caller doesn't yet understand.
+    pub async fn recv_raw(&mut self) -> Option<String> {
+        self.reader_rx.recv().await
+    }
+}
+
+/// A cloneable handle for sending framed messages to a language server,
+/// independent of the `Transport` that reads its responses.
+#[derive(Clone)]
+pub struct TransportSender(mpsc::UnboundedSender<String>);
+
+impl TransportSender {
+    /// Serializes `message` and sends it to the language server, framed with
+    /// a `Content-Length` header.
+    pub fn send<T: Serialize>(&self, message: &JsonRpcMessage<'_, T>) -> Result<()> {
+        let body = serde_json::to_string(message)?;
+        self.0
+            .send(body)
+            .map_err(|_| anyhow!("transport writer task has shut down"))
+    }
+}
+
+/// Drains outgoing messages and writes each as a `Content-Length`-framed
+/// block to the child's stdin.
+async fn writer_loop(mut stdin: ChildStdin, mut rx: mpsc::UnboundedReceiver<String>) {
+    while let Some(body) = rx.recv().await {
+        let header = format!("Content-Length: {}\r\n\r\n", body.len());
+        if stdin.write_all(header.as_bytes()).await.is_err() {
+            break;
+        }
+        if stdin.write_all(body.as_bytes()).await.is_err() {
+            break;
+        }
+        if stdin.flush().await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Reads framed messages from the child's stdout and forwards each decoded
+/// body to `tx` until the stream ends or a frame fails to parse.
+async fn reader_loop(stdout: ChildStdout, tx: mpsc::UnboundedSender<String>) {
+    let mut reader = BufReader::new(stdout);
+    while let Ok(Some(body)) = read_message(&mut reader).await {
+        if tx.send(body).is_err() {
+            break;
+        }
+    }
+}
+
+/// Reads a single `Content-Length`-framed message, or `None` at EOF.
+async fn read_message<R: AsyncBufReadExt + AsyncReadExt + Unpin>(
+    reader: &mut R,
+) -> Result<Option<String>> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = Some(value.trim().parse()?);
+        }
+        // Other headers, e.g. `Content-Type`, are accepted but ignored.
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| anyhow!("message is missing the Content-Length header"))?;
+    let mut buf = vec![0u8; content_length];
+    reader.read_exact(&mut buf).await?;
+    Ok(Some(String::from_utf8(buf)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn reader(bytes: &[u8]) -> BufReader<Cursor<Vec<u8>>> {
+        BufReader::new(Cursor::new(bytes.to_vec()))
+    }
+
+    #[tokio::test]
+    async fn reads_a_well_formed_message() {
+        let mut r = reader(b"Content-Length: 13\r\n\r\n{\"ok\":true}\r\n");
+        assert_eq!(
+            read_message(&mut r).await.unwrap(),
+            Some("{\"ok\":true}\r\n".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_content_type_alongside_content_length() {
+        let mut r = reader(
+            b"Content-Length: 5\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\nhello",
+        );
+        assert_eq!(read_message(&mut r).await.unwrap(), Some("hello".to_string()));
+    }
+
+    #[tokio::test]
+    async fn errors_on_missing_content_length_header() {
+        let mut r = reader(b"Content-Type: application/json\r\n\r\nhello");
+        assert!(read_message(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn errors_on_non_numeric_content_length() {
+        let mut r = reader(b"Content-Length: not-a-number\r\n\r\nhello");
+        assert!(read_message(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn errors_on_truncated_body() {
+        // Claims 10 bytes but only 2 follow.
+        let mut r = reader(b"Content-Length: 10\r\n\r\nhi");
+        assert!(read_message(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn errors_on_invalid_utf8_body() {
+        let mut bytes = b"Content-Length: 2\r\n\r\n".to_vec();
+        bytes.extend_from_slice(&[0xff, 0xfe]);
+        let mut r = reader(&bytes);
+        assert!(read_message(&mut r).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn returns_none_at_eof() {
+        let mut r = reader(b"");
+        assert_eq!(read_message(&mut r).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::capabilities::ServerCapabilities;
+use crate::encoding::OffsetEncoding;
+
+/// Parameters for the `initialize` request.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#initialize)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InitializeParams {
+    #[serde(rename = "processId")]
+    pub process_id: Option<u32>,
+    #[serde(rename = "rootUri")]
+    pub root_uri: Option<String>,
+    pub capabilities: ClientCapabilities,
+}
+
+/// The capabilities the client advertises to the server during `initialize`.
+/// Kept minimal; grown as the client gains the features it describes.
+///
+/// Defaults to advertising `general.positionEncodings`, since `encoding`
+/// already implements conversions for all three kinds and there is no
+/// reason to let the server assume the UTF-16 default when it doesn't have
+/// to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientCapabilities {
+    #[serde(default)]
+    pub general: Option<GeneralClientCapabilities>,
+}
+
+impl Default for ClientCapabilities {
+    fn default() -> Self {
+        Self {
+            general: Some(GeneralClientCapabilities::default()),
+        }
+    }
+}
+
+/// Capabilities that apply to the client as a whole rather than to a
+/// specific feature.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#generalClientCapabilities)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneralClientCapabilities {
+    /// The position encodings this client supports, in order of preference.
+    /// Advertised so the server can pick one instead of both sides assuming
+    /// the UTF-16 default.
+    #[serde(rename = "positionEncodings", default)]
+    pub position_encodings: Option<Vec<OffsetEncoding>>,
+}
+
+impl Default for GeneralClientCapabilities {
+    fn default() -> Self {
+        Self {
+            position_encodings: Some(vec![
+                OffsetEncoding::Utf8,
+                OffsetEncoding::Utf16,
+                OffsetEncoding::Utf32,
+            ]),
+        }
+    }
+}
+
+/// The result of a successful `initialize` request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InitializeResult {
+    pub capabilities: ServerCapabilities,
+}
+
+/// A zero-based position within a text document, expressed as a line and a
+/// character offset within that line.
+/// [See the LSP specification for more details.](https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#position)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// A range between two positions in a text document.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Identifies a text document by its URI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDocumentIdentifier {
+    pub uri: String,
+}
+
+/// A text document together with its full contents, sent when the document
+/// is opened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDocumentItem {
+    pub uri: String,
+    #[serde(rename = "languageId")]
+    pub language_id: String,
+    pub version: i32,
+    pub text: String,
+}
+
+/// Parameters shared by every request that targets a position within a text
+/// document (hover, completion, definition, rename, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDocumentPositionParams {
+    #[serde(rename = "textDocument")]
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+/// Parameters for the `textDocument/hover` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoverParams {
+    #[serde(flatten)]
+    pub text_document_position: TextDocumentPositionParams,
+}
+
+/// The result of a `textDocument/hover` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hover {
+    /// Either a plain string or a `MarkupContent` object; left untyped since
+    /// this client does not render hover contents itself.
+    pub contents: serde_json::Value,
+    pub range: Option<Range>,
+}
+
+/// Parameters for the `textDocument/completion` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionParams {
+    #[serde(flatten)]
+    pub text_document_position: TextDocumentPositionParams,
+}
+
+/// A single completion suggestion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionItem {
+    pub label: String,
+    pub detail: Option<String>,
+    pub documentation: Option<serde_json::Value>,
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+/// The result of a `textDocument/completion` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionList {
+    #[serde(rename = "isIncomplete")]
+    pub is_incomplete: bool,
+    pub items: Vec<CompletionItem>,
+}
+
+/// A single text replacement within a document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub range: Range,
+    #[serde(rename = "newText")]
+    pub new_text: String,
+}
+
+/// A set of text edits to apply across one or more documents, keyed by
+/// document URI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceEdit {
+    pub changes: Option<HashMap<String, Vec<TextEdit>>>,
+}
+
+/// Parameters for the `textDocument/rename` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameParams {
+    #[serde(flatten)]
+    pub text_document_position: TextDocumentPositionParams,
+    #[serde(rename = "newName")]
+    pub new_name: String,
+}
+
+/// Options controlling how a document should be formatted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FormattingOptions {
+    #[serde(rename = "tabSize")]
+    pub tab_size: u32,
+    #[serde(rename = "insertSpaces")]
+    pub insert_spaces: bool,
+}
+
+/// Parameters for the `textDocument/formatting` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentFormattingParams {
+    #[serde(rename = "textDocument")]
+    pub text_document: TextDocumentIdentifier,
+    pub options: FormattingOptions,
+}
+
+/// Parameters for the `textDocument/didOpen` notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidOpenTextDocumentParams {
+    #[serde(rename = "textDocument")]
+    pub text_document: TextDocumentItem,
+}
+
+/// A change to a text document, either the full new text or an incremental
+/// edit, depending on what the server negotiated via `TextDocumentSyncKind`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextDocumentContentChangeEvent {
+    pub range: Option<Range>,
+    pub text: String,
+}
+
+/// Identifies a text document together with the version of the edit being
+/// described.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedTextDocumentIdentifier {
+    pub uri: String,
+    pub version: i32,
+}
+
+/// Parameters for the `textDocument/didChange` notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidChangeTextDocumentParams {
+    #[serde(rename = "textDocument")]
+    pub text_document: VersionedTextDocumentIdentifier,
+    #[serde(rename = "contentChanges")]
+    pub content_changes: Vec<TextDocumentContentChangeEvent>,
+}
+
+/// Parameters for the `textDocument/didClose` notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidCloseTextDocumentParams {
+    #[serde(rename = "textDocument")]
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// Parameters for the `$/cancelRequest` notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelParams {
+    pub id: u64,
+}